@@ -0,0 +1,126 @@
+//! Naive-Bayes tag suggestions from video filenames.
+//!
+//! For every tag option, a [`TagModel`] keeps per-token counts over the
+//! filenames of videos that carry the tag (positive class) versus those
+//! that don't (negative class). Suggestions are the tags whose log-posterior
+//! (with Laplace smoothing) beats their complement for a given filename.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Splits a filename into lowercase alphanumeric tokens, e.g.
+/// `My.Cool-Clip_2023.mp4` -> `["my", "cool", "clip", "2023"]`. The
+/// extension is stripped first since it's constant across the library and
+/// would otherwise just be noise for the classifier.
+pub fn tokenize(filename: &str) -> Vec<String> {
+    let stem = Path::new(filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| filename.to_string());
+    stem.split(|c: char| !c.is_alphanumeric())
+        .filter(|tok| !tok.is_empty())
+        .map(|tok| tok.to_lowercase())
+        .collect()
+}
+
+/// Per-tag token counts for the positive (has tag) and negative (doesn't)
+/// classes, plus the document counts needed for the class priors.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TagModel {
+    positive_docs: usize,
+    negative_docs: usize,
+    positive_token_counts: HashMap<String, usize>,
+    negative_token_counts: HashMap<String, usize>,
+}
+
+impl TagModel {
+    fn log_posterior(&self, tokens: &[String], vocab_size: usize, positive: bool) -> f64 {
+        let (docs, other_docs, token_counts) = if positive {
+            (self.positive_docs, self.negative_docs, &self.positive_token_counts)
+        } else {
+            (self.negative_docs, self.positive_docs, &self.negative_token_counts)
+        };
+        let total_docs = (docs + other_docs).max(1) as f64;
+        let prior = (docs.max(1) as f64 / total_docs).ln();
+
+        let total_tokens: usize = token_counts.values().sum();
+        let log_likelihood: f64 = tokens
+            .iter()
+            .map(|tok| {
+                let count = *token_counts.get(tok).unwrap_or(&0) as f64;
+                ((count + 1.0) / (total_tokens as f64 + vocab_size as f64)).ln()
+            })
+            .sum();
+
+        prior + log_likelihood
+    }
+}
+
+/// Naive-Bayes suggestion model over every tag option, trained from the
+/// current tag database. Persisted as part of `tags.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AutoTagModel {
+    models: HashMap<String, TagModel>,
+}
+
+impl AutoTagModel {
+    /// Retrains from scratch against every tag option and every tagged
+    /// video's filename. Called whenever `Save tags` is clicked.
+    pub fn retrain(
+        options: &HashSet<String>,
+        db: &HashMap<String, HashSet<String>>,
+        filenames: &HashMap<String, String>,
+    ) -> Self {
+        let mut models: HashMap<String, TagModel> = HashMap::new();
+        for tag in options {
+            models.insert(tag.clone(), TagModel::default());
+        }
+
+        for (hash, tags) in db.iter() {
+            let Some(filename) = filenames.get(hash) else {
+                continue;
+            };
+            let tokens = tokenize(filename);
+            for tag in options {
+                let model = models.get_mut(tag).unwrap();
+                let has_tag = tags.contains(tag);
+                let token_counts = if has_tag {
+                    model.positive_docs += 1;
+                    &mut model.positive_token_counts
+                } else {
+                    model.negative_docs += 1;
+                    &mut model.negative_token_counts
+                };
+                for tok in tokens.iter() {
+                    *token_counts.entry(tok.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+
+        Self { models }
+    }
+
+    /// Tags whose posterior beats their complement for `filename`.
+    pub fn suggest(&self, filename: &str) -> HashSet<String> {
+        let tokens = tokenize(filename);
+        let vocab_size: HashSet<&String> = self
+            .models
+            .values()
+            .flat_map(|m| m.positive_token_counts.keys().chain(m.negative_token_counts.keys()))
+            .collect();
+        let vocab_size = vocab_size.len().max(1);
+
+        self.models
+            .iter()
+            .filter(|(_, model)| {
+                model.log_posterior(&tokens, vocab_size, true)
+                    > model.log_posterior(&tokens, vocab_size, false)
+            })
+            .map(|(tag, _)| tag.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,66 @@
+//! Small BK-tree for nearest-neighbour lookups under a discrete distance metric
+//! (used here for Hamming distance between perceptual hashes).
+
+struct Node<T> {
+    item: T,
+    /// children keyed by their distance from `item`
+    children: Vec<(u32, Node<T>)>,
+}
+
+pub struct BkTree<T> {
+    root: Option<Node<T>>,
+    distance: fn(&T, &T) -> u32,
+}
+
+impl<T> BkTree<T> {
+    pub fn new(distance: fn(&T, &T) -> u32) -> Self {
+        Self {
+            root: None,
+            distance,
+        }
+    }
+
+    pub fn insert(&mut self, item: T) {
+        match &mut self.root {
+            None => self.root = Some(Node { item, children: vec![] }),
+            Some(root) => Self::insert_node(root, item, self.distance),
+        }
+    }
+
+    fn insert_node(node: &mut Node<T>, item: T, distance: fn(&T, &T) -> u32) {
+        let d = distance(&node.item, &item);
+        match node.children.iter_mut().find(|(cd, _)| *cd == d) {
+            Some((_, child)) => Self::insert_node(child, item, distance),
+            None => node.children.push((d, Node { item, children: vec![] })),
+        }
+    }
+
+    /// Returns every stored item within `tolerance` of `query`.
+    pub fn find_within(&self, query: &T, tolerance: u32) -> Vec<&T> {
+        let mut out = vec![];
+        if let Some(root) = &self.root {
+            Self::search_node(root, query, tolerance, self.distance, &mut out);
+        }
+        out
+    }
+
+    fn search_node<'a>(
+        node: &'a Node<T>,
+        query: &T,
+        tolerance: u32,
+        distance: fn(&T, &T) -> u32,
+        out: &mut Vec<&'a T>,
+    ) {
+        let d = distance(&node.item, query);
+        if d <= tolerance {
+            out.push(&node.item);
+        }
+        let lo = d.saturating_sub(tolerance);
+        let hi = d + tolerance;
+        for (cd, child) in node.children.iter() {
+            if *cd >= lo && *cd <= hi {
+                Self::search_node(child, query, tolerance, distance, out);
+            }
+        }
+    }
+}
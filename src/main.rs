@@ -1,6 +1,17 @@
+mod autotag;
+mod bktree;
+mod media_source;
+mod organize;
+mod phash;
+
+use autotag::AutoTagModel;
+use bktree::BkTree;
 use eframe::NativeOptions;
-use egui::{CentralPanel, Sense, Slider, TextEdit, Window};
+use egui::{CentralPanel, Color32, ColorImage, RichText, Sense, Slider, TextEdit, TextureHandle, Window};
 use egui_video::{AudioDevice, Player};
+use media_source::{MediaRef, MediaSource};
+use organize::{LinkMode, Manifest, PlannedOp};
+use phash::{PerceptualHashes, VideoHash};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
@@ -37,7 +48,7 @@ fn has_allowed_extension(path: &PathBuf) -> bool {
         .is_some()
 }
 
-fn write_json<S>(name: &str, serializable: &S)
+pub(crate) fn write_json<S>(name: &str, serializable: &S)
 where
     S: Serialize,
 {
@@ -50,7 +61,7 @@ where
     println!("wrote {filename}");
 }
 
-fn load_json<D>(name: &str) -> D
+pub(crate) fn load_json<D>(name: &str) -> D
 where
     D: DeserializeOwned,
 {
@@ -59,31 +70,72 @@ where
     serde_json::from_str(file_contents.as_str()).expect("{filename} deserialize err")
 }
 
-/// expects to be handed a list of files from the same folder
+/// expects to be handed a list of files from the same folder. Only files
+/// that are new (by name) or modified more recently than their cache entry
+/// get re-hashed; `update` forces every file to be re-hashed regardless.
 fn folder_hashes(paths: &Vec<PathBuf>, update: bool) -> HashMap<String, PathBuf> {
     let parent = paths[0].parent().unwrap();
     let hash_path = parent.join(".hashes");
     let hash_filename = hash_path.to_str().unwrap();
-    let fbh: FilesByHash = if parent.join(".hashes.json").exists() && !update {
+
+    let mut fbh: FilesByHash = if parent.join(".hashes.json").exists() {
         load_json(hash_filename)
     } else {
-        let temp = FilesByHash {
-            db: paths
-                .iter()
-                .map(|vid| {
-                    (
-                        file_hash(vid),
-                        String::from(vid.file_name().unwrap().to_str().unwrap()),
-                    )
-                })
-                .collect(),
-        };
-        write_json(hash_filename, &temp);
-        temp
+        FilesByHash { db: HashMap::new() }
     };
+
+    let hash_by_filename: HashMap<String, String> = fbh
+        .db
+        .iter()
+        .map(|(hash, entry)| (entry.filename.clone(), hash.clone()))
+        .collect();
+
+    let current_filenames: HashSet<String> = paths
+        .iter()
+        .map(|p| String::from(p.file_name().unwrap().to_str().unwrap()))
+        .collect();
+    let stale_hashes: Vec<String> = fbh
+        .db
+        .iter()
+        .filter(|(_, entry)| !current_filenames.contains(&entry.filename))
+        .map(|(hash, _)| hash.clone())
+        .collect();
+    let mut changed = !stale_hashes.is_empty();
+    for hash in stale_hashes {
+        fbh.db.remove(&hash);
+    }
+
+    for path in paths.iter() {
+        let filename = String::from(path.file_name().unwrap().to_str().unwrap());
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        let existing = hash_by_filename.get(&filename).and_then(|hash| fbh.db.get(hash));
+        let stale = match (existing, modified) {
+            (Some(entry), Some(modified)) => update || modified > entry.modified,
+            (Some(_), None) => update,
+            (None, _) => true,
+        };
+        if stale {
+            if let Some(old_hash) = hash_by_filename.get(&filename) {
+                fbh.db.remove(old_hash);
+            }
+            fbh.db.insert(
+                file_hash(path),
+                FileEntry {
+                    filename,
+                    modified: modified.unwrap_or(std::time::SystemTime::UNIX_EPOCH),
+                },
+            );
+            changed = true;
+        }
+    }
+
+    if changed {
+        write_json(hash_filename, &fbh);
+    }
+
     fbh.db
         .iter()
-        .map(|(hash, filename)| (hash.clone(), parent.join(filename)))
+        .map(|(hash, entry)| (hash.clone(), parent.join(&entry.filename)))
         .collect()
 }
 
@@ -92,6 +144,9 @@ struct Tags {
     options: HashSet<String>,
     /// indexed by video hash string
     db: HashMap<String, HashSet<String>>,
+    /// filename-based auto-tag suggestion model, retrained on every save
+    #[serde(default)]
+    auto_tag_model: AutoTagModel,
 }
 
 impl Tags {
@@ -99,27 +154,73 @@ impl Tags {
         Self {
             options: HashSet::new(),
             db: HashMap::new(),
+            auto_tag_model: AutoTagModel::default(),
         }
     }
 }
 
+/// A file's name (relative to its folder) and the modified-time it had when
+/// last hashed, used to detect staleness without re-reading the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FileEntry {
+    filename: String,
+    modified: std::time::SystemTime,
+}
+
+/// A tag's role in the current query: required, excluded, or not constrained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterState {
+    Require,
+    Exclude,
+    Ignore,
+}
+
+/// How multiple `Require`d tags combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterMode {
+    And,
+    Or,
+}
+
 /// Stores a local file reference (as absolute paths can still be relative to mountpoint...)
 #[derive(Debug, Serialize, Deserialize)]
 struct FilesByHash {
-    db: HashMap<String, String>,
+    db: HashMap<String, FileEntry>,
 }
 
 /// Heavily based on egui-video example...
 struct App {
     audio_device: AudioDevice,
     player: Option<Player>,
-    videos: Vec<PathBuf>,
-    paths_from_hash: HashMap<String, PathBuf>,
-    videos_filtered: Vec<PathBuf>,
+    videos: Vec<MediaRef>,
+    paths_from_hash: HashMap<String, MediaRef>,
+    videos_filtered: Vec<MediaRef>,
     update_hashes_on_load: bool,
+    /// sources loaded so far (local folders and/or remote servers)
+    loaded_sources: Vec<MediaSource>,
+    remote_source_window_open: bool,
+    remote_base_url: String,
+    remote_index_url: String,
+    remote_load_error: Option<String>,
     tags: Tags,
-    tag_filter: HashSet<String>,
+    tag_filter: HashMap<String, FilterState>,
+    filter_mode: FilterMode,
     media_idx: Option<usize>,
+    /// perceptual fingerprint caches, one per loaded folder (next to `.hashes.json`)
+    phash_caches: HashMap<PathBuf, PerceptualHashes>,
+    dup_tolerance: u32,
+    dup_window_open: bool,
+    /// groups of sha256 hashes considered near-duplicates of one another
+    dup_clusters: Vec<Vec<String>>,
+    dup_preview_textures: HashMap<String, TextureHandle>,
+    /// sha256 hash (or remote id) of the video at `media_idx`, resolved once per index change
+    /// via `paths_from_hash` instead of re-reading/re-hashing the file
+    current_media_hash: Option<(usize, String)>,
+    organize_window_open: bool,
+    organize_target: Option<PathBuf>,
+    organize_mode: LinkMode,
+    organize_preview: Vec<PlannedOp>,
+    organize_last_manifest: Option<Manifest>,
 }
 
 impl Default for App {
@@ -144,65 +245,193 @@ impl Default for App {
             paths_from_hash: HashMap::new(),
             videos_filtered: vec![],
             update_hashes_on_load: false,
+            loaded_sources: vec![],
+            remote_source_window_open: false,
+            remote_base_url: String::new(),
+            remote_index_url: String::new(),
+            remote_load_error: None,
             tags,
-            tag_filter: HashSet::new(),
+            tag_filter: HashMap::new(),
+            filter_mode: FilterMode::And,
             media_idx: None,
             player: None,
+            phash_caches: HashMap::new(),
+            dup_tolerance: 8,
+            dup_window_open: false,
+            dup_clusters: vec![],
+            dup_preview_textures: HashMap::new(),
+            current_media_hash: None,
+            organize_window_open: false,
+            organize_target: None,
+            organize_mode: LinkMode::HardLink,
+            organize_preview: vec![],
+            organize_last_manifest: None,
         }
     }
 }
 
 impl App {
     fn new_player(&mut self, ctx: &egui::Context) {
-        let media_path = self.media_idx.map_or(String::new(), |i| {
-            String::from(self.videos_filtered[i].to_str().unwrap())
-        }); // empty if idx is None
+        let media_path = self
+            .media_idx
+            .map_or(String::new(), |i| self.videos_filtered[i].player_source()); // empty if idx is None
         self.player = Player::new(ctx, &media_path.replace("\"", ""))
             .and_then(|p| p.with_audio(&mut self.audio_device))
             .ok()
     }
 
+    /// Resolves the hash/id of the video at `media_idx`, caching it until
+    /// `media_idx` changes so a local file isn't re-read and re-hashed every
+    /// frame. Remote items already carry their own id, so no lookup is
+    /// needed for them.
+    fn current_media_hash(&mut self) -> Option<String> {
+        let idx = self.media_idx?;
+        if self.current_media_hash.as_ref().map(|(i, _)| *i) != Some(idx) {
+            let media = &self.videos_filtered[idx];
+            self.current_media_hash = match media {
+                MediaRef::Remote { id, .. } => Some((idx, id.clone())),
+                MediaRef::Local(_) => self
+                    .paths_from_hash
+                    .iter()
+                    .find(|(_, m)| *m == media)
+                    .map(|(hash, _)| (idx, hash.clone())),
+            };
+        }
+        self.current_media_hash.as_ref().map(|(_, hash)| hash.clone())
+    }
+
     fn load_folder(&mut self, path_buf: PathBuf) {
-        let mut vids: Vec<_> = fs::read_dir(path_buf)
+        let mut vids: Vec<_> = fs::read_dir(&path_buf)
             .expect("could not read folder?")
             .filter_map(|entry| entry.ok().map(|e| e.path()))
             .filter(has_allowed_extension)
             .collect();
         for (hash, pb) in folder_hashes(&vids, self.update_hashes_on_load) {
-            self.paths_from_hash.insert(hash, pb);
+            self.paths_from_hash.insert(hash, MediaRef::Local(pb));
+        }
+        self.videos.extend(vids.drain(..).map(MediaRef::Local));
+        self.loaded_sources.push(MediaSource::LocalFolder(path_buf));
+    }
+
+    /// Fetches a remote server's JSON item listing and adds its videos,
+    /// keyed on the server-provided id rather than a sha256 hash.
+    fn load_remote(&mut self, base_url: String, index_url: String) -> Result<(), String> {
+        let items = media_source::fetch_remote_index(&index_url)?;
+        for item in items {
+            let media = MediaRef::Remote {
+                id: item.id.clone(),
+                name: item.name,
+                url: item.stream_url,
+            };
+            self.paths_from_hash.insert(item.id, media.clone());
+            self.videos.push(media);
+        }
+        self.loaded_sources.push(MediaSource::Remote { base_url, index_url });
+        Ok(())
+    }
+
+    /// Re-fetches every previously loaded folder/remote source from scratch,
+    /// e.g. to pick up files added to a folder or a remote server's listing
+    /// without restarting the app.
+    fn reload_sources(&mut self, ctx: &egui::Context) {
+        let sources = std::mem::take(&mut self.loaded_sources);
+        self.videos.clear();
+        self.paths_from_hash.clear();
+        for source in sources {
+            match source {
+                MediaSource::LocalFolder(path) => self.load_folder(path),
+                MediaSource::Remote { base_url, index_url } => {
+                    if let Err(e) = self.load_remote(base_url, index_url) {
+                        self.remote_load_error = Some(e);
+                    }
+                }
+            }
         }
-        self.videos.append(&mut vids);
+        self.update_filtered();
+        self.new_player(ctx);
+    }
+
+    /// Computes (or fetches cached) perceptual fingerprints for every loaded
+    /// video, then groups near-duplicates within `self.dup_tolerance` bits.
+    fn find_duplicates(&mut self) {
+        let mut fingerprints: Vec<(String, VideoHash)> = vec![];
+        for (hash, media) in self.paths_from_hash.iter() {
+            // remote items can't be read in full to fingerprint locally
+            let Some(path) = media.local_path() else {
+                continue;
+            };
+            let Some(folder) = path.parent() else {
+                continue;
+            };
+            let cache = self
+                .phash_caches
+                .entry(folder.to_path_buf())
+                .or_insert_with(|| PerceptualHashes::load_or_default(folder));
+            if let Some(fp) = cache.get_or_compute(hash, path) {
+                fingerprints.push((hash.clone(), fp.clone()));
+            }
+        }
+        for (folder, cache) in self.phash_caches.iter() {
+            cache.save(folder);
+        }
+
+        let mut tree = BkTree::new(|a: &(String, VideoHash), b: &(String, VideoHash)| a.1.hamming(&b.1));
+        for fp in fingerprints.iter() {
+            tree.insert(fp.clone());
+        }
+        let by_hash: HashMap<String, VideoHash> = fingerprints.into_iter().collect();
+        self.dup_clusters = phash::cluster_duplicates(&by_hash, &tree, self.dup_tolerance);
+        self.dup_preview_textures.clear();
+        self.dup_window_open = true;
     }
 
     fn update_filtered(&mut self) {
+        let required: Vec<&String> = self
+            .tag_filter
+            .iter()
+            .filter(|(_, state)| **state == FilterState::Require)
+            .map(|(tag, _)| tag)
+            .collect();
+        let excluded: Vec<&String> = self
+            .tag_filter
+            .iter()
+            .filter(|(_, state)| **state == FilterState::Exclude)
+            .map(|(tag, _)| tag)
+            .collect();
+
         self.videos_filtered = Vec::from_iter(
             self.paths_from_hash
                 .iter()
                 .filter(|(hash, _)| {
-                    self.tag_filter
-                        .iter()
-                        .find(|tag| {
-                            if !self.tags.db.contains_key(*hash) || self.tags.db.len() == 0 {
-                                true
-                            } else {
-                                !self.tags.db[*hash].contains(*tag)
-                            }
-                        })
-                        .is_none()
+                    let empty = HashSet::new();
+                    let tags = self.tags.db.get(*hash).unwrap_or(&empty);
+
+                    let passes_required = if required.is_empty() {
+                        true
+                    } else {
+                        match self.filter_mode {
+                            FilterMode::And => required.iter().all(|tag| tags.contains(*tag)),
+                            FilterMode::Or => required.iter().any(|tag| tags.contains(*tag)),
+                        }
+                    };
+                    let passes_excluded = excluded.iter().all(|tag| !tags.contains(*tag));
+                    passes_required && passes_excluded
                 })
                 .map(|(_, pb)| pb.clone()),
         );
-        if self.videos_filtered.len() > 0 {
-            if let None = self.media_idx {
-                self.media_idx = if self.videos_filtered.len() > 0 {
-                    Some(0)
-                } else {
-                    None
-                };
-            }
+        let n = self.videos_filtered.len();
+        self.media_idx = if n == 0 {
+            None
         } else {
-            self.media_idx = None;
-        }
+            // clamp rather than just defaulting to 0, so a shrinking result
+            // set can't leave media_idx pointing past the end of the vec
+            Some(self.media_idx.unwrap_or(0).min(n - 1))
+        };
+        // videos_filtered was just rebuilt from HashMap iteration order, so a
+        // given index can now point at a different video even if media_idx
+        // itself didn't change; drop the stale hash cache rather than let
+        // the Tags panel read/write against the wrong video.
+        self.current_media_hash = None;
     }
 }
 
@@ -234,20 +463,65 @@ impl eframe::App for App {
                         self.new_player(ctx);
                     }
                 }
+                if ui.button("Load remote source").clicked() {
+                    self.remote_source_window_open = true;
+                }
+                if !self.loaded_sources.is_empty() && ui.button("Reload sources").clicked() {
+                    self.reload_sources(ctx);
+                }
             });
             ui.label(format!("{} video files loaded", self.videos.len()));
             ui.horizontal(|ui| {
-                ui.label("Filter for those containing");
+                ui.label("Duplicate detection tolerance (Hamming bits)");
+                ui.add(Slider::new(&mut self.dup_tolerance, 0..=20));
+                if ui.button("Find duplicates").clicked() {
+                    self.find_duplicates();
+                }
+                if ui.button("Organize library").clicked() {
+                    self.organize_window_open = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Match required tags with:");
                 let mut any_changed = false;
-                for opt in self.tags.options.iter() {
-                    let mut temp = self.tag_filter.contains(opt);
-                    if ui.checkbox(&mut temp, opt.clone()).changed() {
-                        any_changed = true;
+                any_changed |= ui.radio_value(&mut self.filter_mode, FilterMode::And, "AND").changed();
+                any_changed |= ui.radio_value(&mut self.filter_mode, FilterMode::Or, "OR").changed();
+                if any_changed {
+                    self.update_filtered();
+                    self.new_player(ctx);
+                }
+            });
+            ui.vertical(|ui| {
+                ui.label("Filter (require / exclude / ignore per tag)");
+                let hash_by_media: HashMap<&MediaRef, &String> =
+                    self.paths_from_hash.iter().map(|(hash, media)| (media, hash)).collect();
+                let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+                for media in self.videos_filtered.iter() {
+                    if let Some(tags) = hash_by_media.get(media).and_then(|hash| self.tags.db.get(*hash)) {
+                        for tag in tags.iter() {
+                            *tag_counts.entry(tag.as_str()).or_insert(0) += 1;
+                        }
                     }
-                    match temp {
-                        true => self.tag_filter.insert(opt.clone()),
-                        false => self.tag_filter.remove(opt),
-                    };
+                }
+
+                let mut any_changed = false;
+                for opt in self.tags.options.iter() {
+                    let state = self.tag_filter.get(opt).copied().unwrap_or(FilterState::Ignore);
+                    let count = tag_counts.get(opt.as_str()).copied().unwrap_or(0);
+                    ui.horizontal(|ui| {
+                        let mut new_state = state;
+                        any_changed |= ui.radio_value(&mut new_state, FilterState::Ignore, "any").changed();
+                        any_changed |= ui.radio_value(&mut new_state, FilterState::Require, "has").changed();
+                        any_changed |= ui.radio_value(&mut new_state, FilterState::Exclude, "not").changed();
+                        ui.label(format!("{opt} ({count})"));
+                        if new_state != state {
+                            if new_state == FilterState::Ignore {
+                                self.tag_filter.remove(opt);
+                            } else {
+                                self.tag_filter.insert(opt.clone(), new_state);
+                            }
+                        }
+                    });
                 }
                 if any_changed {
                     self.update_filtered();
@@ -298,15 +572,24 @@ impl eframe::App for App {
                         });
                     }
                     if let Some(i) = self.media_idx {
-                        let fh = file_hash(&self.videos_filtered[i]);
+                        let Some(fh) = self.current_media_hash() else {
+                            return;
+                        };
                         if !self.tags.db.contains_key(&fh) {
                             self.tags.db.insert(fh.clone(), HashSet::new());
                         }
                         ui.separator();
                         ui.label("Tags");
+                        let filename = self.videos_filtered[i].display_name();
+                        let suggested = self.tags.auto_tag_model.suggest(&filename);
                         for opt in self.tags.options.iter() {
                             let mut temp = self.tags.db[&fh].contains(opt);
-                            ui.checkbox(&mut temp, opt.clone());
+                            let label = if !temp && suggested.contains(opt) {
+                                RichText::new(opt.clone()).color(Color32::GREEN)
+                            } else {
+                                RichText::new(opt.clone())
+                            };
+                            ui.checkbox(&mut temp, label);
                             let vid_tags = self.tags.db.get_mut(&fh).unwrap();
                             match temp {
                                 true => vid_tags.insert(opt.clone()),
@@ -314,11 +597,146 @@ impl eframe::App for App {
                             };
                         }
                         if ui.button("Save tags").clicked() {
+                            let filenames: HashMap<String, String> = self
+                                .paths_from_hash
+                                .iter()
+                                .map(|(hash, media)| (hash.clone(), media.display_name()))
+                                .collect();
+                            self.tags.auto_tag_model =
+                                AutoTagModel::retrain(&self.tags.options, &self.tags.db, &filenames);
                             write_json("tags", &self.tags);
                         }
                     }
                 }
             });
         });
+
+        let mut window_open = self.dup_window_open;
+        Window::new("Duplicate videos")
+            .open(&mut window_open)
+            .show(ctx, |ui| {
+                if self.dup_clusters.is_empty() {
+                    ui.label("No near-duplicates found.");
+                }
+                for cluster in self.dup_clusters.clone().iter() {
+                    ui.separator();
+                    ui.horizontal_wrapped(|ui| {
+                        for hash in cluster {
+                            let Some(path) = self.paths_from_hash.get(hash).and_then(|m| m.local_path()) else {
+                                continue;
+                            };
+                            ui.vertical(|ui| {
+                                if !self.dup_preview_textures.contains_key(hash) {
+                                    if let Ok((w, h, rgba)) = phash::thumbnail_for_display(path, 96) {
+                                        let image = ColorImage::from_rgba_unmultiplied([w, h], &rgba);
+                                        let texture =
+                                            ctx.load_texture(hash.clone(), image, Default::default());
+                                        self.dup_preview_textures.insert(hash.clone(), texture);
+                                    }
+                                }
+                                if let Some(texture) = self.dup_preview_textures.get(hash) {
+                                    ui.image(texture);
+                                }
+                                ui.label(path.file_name().unwrap().to_string_lossy());
+                            });
+                        }
+                    });
+                }
+            });
+        self.dup_window_open = window_open;
+
+        let mut organize_window_open = self.organize_window_open;
+        Window::new("Organize library")
+            .open(&mut organize_window_open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Target folder:");
+                    ui.label(
+                        self.organize_target
+                            .as_ref()
+                            .map(|p| p.display().to_string())
+                            .unwrap_or_else(|| "none selected".to_string()),
+                    );
+                    if ui.button("Choose...").clicked() {
+                        if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                            self.organize_target = Some(dir);
+                            self.organize_preview.clear();
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.organize_mode, LinkMode::HardLink, "Hard link");
+                    ui.radio_value(&mut self.organize_mode, LinkMode::Copy, "Copy");
+                });
+                if let Some(target) = self.organize_target.clone() {
+                    if ui.button("Preview (dry run)").clicked() {
+                        self.organize_preview = organize::plan(&self.tags.db, &self.paths_from_hash, &target);
+                    }
+                    if !self.organize_preview.is_empty() {
+                        ui.label(format!("{} files would be placed:", self.organize_preview.len()));
+                        egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                            for op in self.organize_preview.iter() {
+                                ui.label(op.dest.display().to_string());
+                            }
+                        });
+                        if ui.button("Execute").clicked() {
+                            match organize::execute(&self.organize_preview, self.organize_mode) {
+                                Ok(manifest) => {
+                                    manifest.save(&target);
+                                    self.organize_last_manifest = Some(manifest);
+                                    self.organize_preview.clear();
+                                }
+                                Err(e) => eprintln!("organize failed: {e}"),
+                            }
+                        }
+                    }
+                    let manifest = self.organize_last_manifest.clone().or_else(|| Manifest::load(&target));
+                    if let Some(manifest) = manifest {
+                        if manifest.skipped > 0 {
+                            ui.label(format!(
+                                "{} destinations already existed and were left untouched",
+                                manifest.skipped
+                            ));
+                        }
+                        if !manifest.created.is_empty() && ui.button("Revert last organize").clicked() {
+                            if let Err(e) = organize::revert(&manifest) {
+                                eprintln!("revert failed: {e}");
+                            }
+                            self.organize_last_manifest = Some(Manifest::default());
+                        }
+                    }
+                } else {
+                    ui.label("Choose a target folder to begin.");
+                }
+            });
+        self.organize_window_open = organize_window_open;
+
+        let mut remote_source_window_open = self.remote_source_window_open;
+        Window::new("Load remote source")
+            .open(&mut remote_source_window_open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Base URL:");
+                    ui.text_edit_singleline(&mut self.remote_base_url);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Index URL:");
+                    ui.text_edit_singleline(&mut self.remote_index_url);
+                });
+                if ui.button("Load").clicked() {
+                    match self.load_remote(self.remote_base_url.clone(), self.remote_index_url.clone()) {
+                        Ok(()) => {
+                            self.remote_load_error = None;
+                            self.update_filtered();
+                            self.new_player(ctx);
+                        }
+                        Err(e) => self.remote_load_error = Some(e),
+                    }
+                }
+                if let Some(err) = &self.remote_load_error {
+                    ui.colored_label(Color32::RED, err);
+                }
+            });
+        self.remote_source_window_open = remote_source_window_open;
     }
 }
@@ -0,0 +1,77 @@
+//! Generalizes where a video library's media lives: a local folder read with
+//! `fs::read_dir`, or a remote HTTP media server that serves a JSON listing
+//! and streamable URLs. [`MediaRef`] is what the rest of the app (playback,
+//! tagging, filtering) actually holds per item, so it doesn't need to care
+//! which kind of [`MediaSource`] it came from.
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// Where a batch of loaded videos came from.
+#[derive(Debug, Clone)]
+pub enum MediaSource {
+    LocalFolder(PathBuf),
+    Remote { base_url: String, index_url: String },
+}
+
+/// One entry in a remote server's JSON listing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteItem {
+    pub id: String,
+    pub name: String,
+    pub stream_url: String,
+}
+
+/// A single piece of media, local or remote. Remote items can't always be
+/// fully downloaded to sha256 them, so they carry their own stable
+/// server-provided id instead, used as the key into `Tags::db` the same way
+/// a local file's sha256 hash would be.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MediaRef {
+    Local(PathBuf),
+    Remote {
+        id: String,
+        name: String,
+        url: String,
+    },
+}
+
+impl MediaRef {
+    /// What gets handed to `Player::new`.
+    pub fn player_source(&self) -> String {
+        match self {
+            MediaRef::Local(path) => path.to_str().unwrap_or_default().to_string(),
+            MediaRef::Remote { url, .. } => url.clone(),
+        }
+    }
+
+    pub fn display_name(&self) -> String {
+        match self {
+            MediaRef::Local(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            MediaRef::Remote { name, .. } => name.clone(),
+        }
+    }
+
+    /// `Some` only for local files, since most local-only operations
+    /// (hashing, hard-linking, frame sampling) need an actual path on disk.
+    pub fn local_path(&self) -> Option<&PathBuf> {
+        match self {
+            MediaRef::Local(path) => Some(path),
+            MediaRef::Remote { .. } => None,
+        }
+    }
+}
+
+/// Fetches a remote server's JSON item listing.
+pub fn fetch_remote_index(index_url: &str) -> Result<Vec<RemoteItem>, String> {
+    let body = ureq::get(index_url)
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
@@ -0,0 +1,170 @@
+//! Materializes the tag database into a plain directory layout: one
+//! subfolder per tag, populated with hard links (or copies) of every video
+//! carrying that tag, so the tagged collection can be browsed without this
+//! app. Operations are previewed in dry-run before anything touches disk,
+//! and a manifest of what was created is kept so the export can be reverted.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::media_source::MediaRef;
+use crate::{load_json, write_json};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkMode {
+    HardLink,
+    Copy,
+}
+
+/// A single planned filesystem operation: place `source` at `dest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedOp {
+    pub source: PathBuf,
+    pub dest: PathBuf,
+}
+
+/// Record of what an organize run created, so it can be reverted.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub created: Vec<PathBuf>,
+    /// destinations that already existed on disk and were left untouched
+    #[serde(default)]
+    pub skipped: usize,
+}
+
+impl Manifest {
+    fn manifest_path(target: &Path) -> String {
+        target.join(".organize_manifest").to_str().unwrap().to_string()
+    }
+
+    pub fn save(&self, target: &Path) {
+        write_json(&Self::manifest_path(target), self);
+    }
+
+    pub fn load(target: &Path) -> Option<Self> {
+        if target.join(".organize_manifest.json").exists() {
+            Some(load_json(&Self::manifest_path(target)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Replaces characters illegal on common filesystems with `_` and collapses
+/// whitespace, so a tag name is always safe to use as a folder name.
+pub fn sanitize_tag_name(tag: &str) -> String {
+    let replaced: String = tag
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let collapsed = replaced.split_whitespace().collect::<Vec<_>>().join(" ");
+    let trimmed = collapsed.trim_matches('.');
+    if trimmed.is_empty() {
+        "untagged".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Appends a short hash/id suffix to a filename ahead of its extension, so
+/// two distinct videos that happen to share a basename don't collide.
+fn disambiguate(file_name: &str, hash: &str) -> String {
+    let suffix = &hash[..hash.len().min(8)];
+    match file_name.rsplit_once('.') {
+        Some((stem, ext)) => format!("{stem}-{suffix}.{ext}"),
+        None => format!("{file_name}-{suffix}"),
+    }
+}
+
+/// Builds the list of (source, dest) pairs an organize run would create,
+/// without touching disk. Remote items have no local file to link or copy,
+/// so they're skipped. Videos that would otherwise collide on the same
+/// destination (same basename, same tag folder) are disambiguated with a
+/// hash/id suffix rather than silently dropped.
+pub fn plan(
+    db: &HashMap<String, HashSet<String>>,
+    paths_from_hash: &HashMap<String, MediaRef>,
+    target: &Path,
+) -> Vec<PlannedOp> {
+    // group by tag folder first, so collisions are detected per-destination-directory
+    let mut by_tag_dir: HashMap<PathBuf, Vec<(String, PathBuf, String)>> = HashMap::new();
+    for (hash, tags) in db.iter() {
+        let Some(source) = paths_from_hash.get(hash).and_then(|m| m.local_path()) else {
+            continue;
+        };
+        let Some(file_name) = source.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        for tag in tags.iter() {
+            let tag_dir = target.join(sanitize_tag_name(tag));
+            by_tag_dir
+                .entry(tag_dir)
+                .or_default()
+                .push((hash.clone(), source.clone(), file_name.to_string()));
+        }
+    }
+
+    let mut ops = vec![];
+    for (tag_dir, entries) in by_tag_dir {
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
+        for (_, _, file_name) in entries.iter() {
+            *name_counts.entry(file_name.as_str()).or_insert(0) += 1;
+        }
+        for (hash, source, file_name) in entries {
+            let dest_name = if name_counts[file_name.as_str()] > 1 {
+                disambiguate(&file_name, &hash)
+            } else {
+                file_name
+            };
+            ops.push(PlannedOp {
+                source,
+                dest: tag_dir.join(dest_name),
+            });
+        }
+    }
+    ops
+}
+
+/// Executes `ops`, creating tag folders as needed, and returns a manifest of
+/// every destination actually created (so it can be reverted later). Since
+/// `plan` already disambiguates basename collisions, a destination that
+/// still exists here is one this (or a prior) run already placed, and is
+/// counted in `Manifest::skipped` rather than silently dropped.
+pub fn execute(ops: &[PlannedOp], mode: LinkMode) -> Result<Manifest, String> {
+    let mut created = vec![];
+    let mut skipped = 0;
+    for op in ops {
+        if let Some(parent) = op.dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        if op.dest.exists() {
+            skipped += 1;
+            continue;
+        }
+        let result = match mode {
+            LinkMode::HardLink => std::fs::hard_link(&op.source, &op.dest),
+            LinkMode::Copy => std::fs::copy(&op.source, &op.dest).map(|_| ()),
+        };
+        result.map_err(|e| format!("{}: {e}", op.dest.display()))?;
+        created.push(op.dest.clone());
+    }
+    Ok(Manifest { created, skipped })
+}
+
+/// Removes every file an organize run created, per its manifest.
+pub fn revert(manifest: &Manifest) -> Result<(), String> {
+    for path in manifest.created.iter() {
+        if path.exists() {
+            std::fs::remove_file(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,300 @@
+//! Perceptual-hash duplicate detection.
+//!
+//! Each video is fingerprinted by sampling `FRAMES_PER_VIDEO` evenly-spaced
+//! frames, downscaling each to a `THUMB_SIZE`x`THUMB_SIZE` grayscale
+//! thumbnail, and keeping the low-frequency bits of its 2D DCT (a classic
+//! pHash). The per-frame bit vectors are concatenated into one fingerprint so
+//! that every video produces a fixed-length bitstring, which is the
+//! invariant the BK-tree Hamming comparison relies on.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{load_json, write_json};
+
+/// Frames sampled per video. Fixed globally so every fingerprint has the
+/// same bit-length.
+pub const FRAMES_PER_VIDEO: usize = 10;
+/// Side length (in pixels) of the grayscale thumbnail each frame is reduced
+/// to before the DCT is taken.
+pub const THUMB_SIZE: usize = 32;
+/// Side length of the low-frequency DCT block kept per frame (excluding the
+/// DC term).
+const DCT_KEEP: usize = 8;
+const BITS_PER_FRAME: usize = DCT_KEEP * DCT_KEEP - 1;
+pub const FINGERPRINT_BITS: usize = BITS_PER_FRAME * FRAMES_PER_VIDEO;
+
+/// A fixed-length perceptual fingerprint, one bit per low-frequency DCT
+/// coefficient across all sampled frames.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoHash(Vec<bool>);
+
+impl VideoHash {
+    /// Hamming distance between two fingerprints of equal length.
+    pub fn hamming(&self, other: &Self) -> u32 {
+        debug_assert_eq!(self.0.len(), other.0.len(), "fingerprints must be the same length");
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .filter(|(a, b)| a != b)
+            .count() as u32
+    }
+}
+
+/// Persisted cache of perceptual fingerprints, keyed by sha256 hash so they
+/// are computed once per video, next to `.hashes.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PerceptualHashes {
+    pub db: HashMap<String, VideoHash>,
+}
+
+impl PerceptualHashes {
+    pub fn load_or_default(folder: &Path) -> Self {
+        let filename = folder.join(".phashes").to_str().unwrap().to_string();
+        if folder.join(".phashes.json").exists() {
+            load_json(&filename)
+        } else {
+            Self::default()
+        }
+    }
+
+    pub fn save(&self, folder: &Path) {
+        let filename = folder.join(".phashes").to_str().unwrap().to_string();
+        write_json(&filename, self);
+    }
+
+    /// Fetches the cached fingerprint for `hash`, computing and inserting it
+    /// from `path` if missing.
+    pub fn get_or_compute(&mut self, hash: &str, path: &PathBuf) -> Option<&VideoHash> {
+        if !self.db.contains_key(hash) {
+            let fp = compute_video_hash(path).ok()?;
+            self.db.insert(hash.to_string(), fp);
+        }
+        self.db.get(hash)
+    }
+}
+
+/// Samples `FRAMES_PER_VIDEO` evenly-spaced frames from the video at `path`
+/// and builds its fingerprint.
+pub fn compute_video_hash(path: &PathBuf) -> Result<VideoHash, String> {
+    let thumbnails = sample_grayscale_thumbnails(path, FRAMES_PER_VIDEO, THUMB_SIZE)?;
+    let mut bits = Vec::with_capacity(FINGERPRINT_BITS);
+    for thumb in thumbnails {
+        bits.extend(frame_phash_bits(&thumb));
+    }
+    Ok(VideoHash(bits))
+}
+
+/// Samples `n` evenly-spaced frames from `path`, each reduced to a
+/// `size`x`size` grayscale thumbnail (row-major, 0.0..=1.0 luma).
+fn sample_grayscale_thumbnails(
+    path: &PathBuf,
+    n: usize,
+    size: usize,
+) -> Result<Vec<Vec<f64>>, String> {
+    let mut input = ffmpeg_next::format::input(path).map_err(|e| e.to_string())?;
+    let video_stream = input
+        .streams()
+        .best(ffmpeg_next::media::Type::Video)
+        .ok_or("no video stream")?;
+    let stream_index = video_stream.index();
+    let duration = input.duration().max(1);
+
+    let mut decoder = ffmpeg_next::codec::context::Context::from_parameters(video_stream.parameters())
+        .map_err(|e| e.to_string())?
+        .decoder()
+        .video()
+        .map_err(|e| e.to_string())?;
+
+    let mut scaler = ffmpeg_next::software::scaling::Context::get(
+        decoder.format(),
+        decoder.width(),
+        decoder.height(),
+        ffmpeg_next::format::Pixel::GRAY8,
+        size as u32,
+        size as u32,
+        ffmpeg_next::software::scaling::Flags::BILINEAR,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut thumbnails = vec![];
+    for i in 0..n {
+        let target_ts = duration * i as i64 / n as i64;
+        input
+            .seek(target_ts, ..target_ts)
+            .map_err(|e| e.to_string())?;
+        decoder.flush();
+
+        'demux: for (stream, packet) in input.packets() {
+            if stream.index() != stream_index {
+                continue;
+            }
+            decoder.send_packet(&packet).map_err(|e| e.to_string())?;
+            let mut decoded = ffmpeg_next::util::frame::Video::empty();
+            if decoder.receive_frame(&mut decoded).is_ok() {
+                let mut gray = ffmpeg_next::util::frame::Video::empty();
+                scaler.run(&decoded, &mut gray).map_err(|e| e.to_string())?;
+                let data = gray.data(0);
+                let stride = gray.stride(0);
+                let pixels = (0..size)
+                    .flat_map(|y| (0..size).map(move |x| (x, y)))
+                    .map(|(x, y)| data[y * stride + x] as f64 / 255.0)
+                    .collect();
+                thumbnails.push(pixels);
+                break 'demux;
+            }
+        }
+    }
+    if thumbnails.len() != n {
+        return Err(format!("only sampled {} of {n} frames", thumbnails.len()));
+    }
+    Ok(thumbnails)
+}
+
+/// Runs a 2D DCT-II over the thumbnail, keeps the top-left `DCT_KEEP`x`DCT_KEEP`
+/// block excluding the DC term, and thresholds each coefficient against the
+/// block's median.
+fn frame_phash_bits(thumbnail: &[f64]) -> Vec<bool> {
+    let size = THUMB_SIZE;
+    let dct = dct_2d(thumbnail, size);
+
+    let mut coeffs = Vec::with_capacity(BITS_PER_FRAME);
+    for v in 0..DCT_KEEP {
+        for u in 0..DCT_KEEP {
+            if u == 0 && v == 0 {
+                continue; // skip DC term
+            }
+            coeffs.push(dct[v * size + u]);
+        }
+    }
+
+    let mut sorted = coeffs.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median = sorted[sorted.len() / 2];
+
+    coeffs.into_iter().map(|c| c > median).collect()
+}
+
+/// Naive separable 2D DCT-II (a 1D pass over rows, then over columns;
+/// O(n^3)), adequate for the small thumbnails used here.
+fn dct_2d(image: &[f64], size: usize) -> Vec<f64> {
+    let alpha = |k: usize| if k == 0 { (1.0 / size as f64).sqrt() } else { (2.0 / size as f64).sqrt() };
+
+    let mut rows = vec![0.0; size * size];
+    for y in 0..size {
+        for u in 0..size {
+            let mut sum = 0.0;
+            for x in 0..size {
+                sum += image[y * size + x]
+                    * (std::f64::consts::PI * (2 * x + 1) as f64 * u as f64 / (2 * size) as f64).cos();
+            }
+            rows[y * size + u] = alpha(u) * sum;
+        }
+    }
+
+    let mut out = vec![0.0; size * size];
+    for u in 0..size {
+        for v in 0..size {
+            let mut sum = 0.0;
+            for y in 0..size {
+                sum += rows[y * size + u]
+                    * (std::f64::consts::PI * (2 * y + 1) as f64 * v as f64 / (2 * size) as f64).cos();
+            }
+            out[v * size + u] = alpha(v) * sum;
+        }
+    }
+    out
+}
+
+/// Grabs a single mid-video frame as RGBA8 for display in the duplicates
+/// window. Separate from the fingerprint sampling since it's only needed
+/// on demand for videos actually shown to the user.
+pub fn thumbnail_for_display(path: &PathBuf, size: usize) -> Result<(usize, usize, Vec<u8>), String> {
+    let mut input = ffmpeg_next::format::input(path).map_err(|e| e.to_string())?;
+    let video_stream = input
+        .streams()
+        .best(ffmpeg_next::media::Type::Video)
+        .ok_or("no video stream")?;
+    let stream_index = video_stream.index();
+    let duration = input.duration().max(1);
+
+    let mut decoder = ffmpeg_next::codec::context::Context::from_parameters(video_stream.parameters())
+        .map_err(|e| e.to_string())?
+        .decoder()
+        .video()
+        .map_err(|e| e.to_string())?;
+
+    let mut scaler = ffmpeg_next::software::scaling::Context::get(
+        decoder.format(),
+        decoder.width(),
+        decoder.height(),
+        ffmpeg_next::format::Pixel::RGBA,
+        size as u32,
+        size as u32,
+        ffmpeg_next::software::scaling::Flags::BILINEAR,
+    )
+    .map_err(|e| e.to_string())?;
+
+    input.seek(duration / 2, ..duration / 2).map_err(|e| e.to_string())?;
+    decoder.flush();
+
+    for (stream, packet) in input.packets() {
+        if stream.index() != stream_index {
+            continue;
+        }
+        decoder.send_packet(&packet).map_err(|e| e.to_string())?;
+        let mut decoded = ffmpeg_next::util::frame::Video::empty();
+        if decoder.receive_frame(&mut decoded).is_ok() {
+            let mut rgba = ffmpeg_next::util::frame::Video::empty();
+            scaler.run(&decoded, &mut rgba).map_err(|e| e.to_string())?;
+            let stride = rgba.stride(0);
+            let data = rgba.data(0);
+            let mut out = Vec::with_capacity(size * size * 4);
+            for y in 0..size {
+                out.extend_from_slice(&data[y * stride..y * stride + size * 4]);
+            }
+            return Ok((size, size, out));
+        }
+    }
+    Err("could not decode a preview frame".to_string())
+}
+
+/// Groups `hashes` (sha256 -> fingerprint) into clusters of near-duplicates,
+/// using `tree` for lookups: each cluster is a seed hash plus every
+/// not-yet-clustered hash within `tolerance` Hamming bits of that seed (two
+/// members of a cluster may be up to twice `tolerance` apart).
+/// Clusters are disjoint: once a hash lands in a cluster it's excluded from
+/// every later one, so no video is ever listed as a duplicate of two
+/// different groups at once.
+pub fn cluster_duplicates(
+    hashes: &HashMap<String, VideoHash>,
+    tree: &crate::bktree::BkTree<(String, VideoHash)>,
+    tolerance: u32,
+) -> Vec<Vec<String>> {
+    let mut visited: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut clusters = vec![];
+    for (hash, fp) in hashes.iter() {
+        if visited.contains(hash) {
+            continue;
+        }
+        let matches = tree.find_within(&(hash.clone(), fp.clone()), tolerance);
+        let group: Vec<String> = matches
+            .into_iter()
+            .map(|(h, _)| h.clone())
+            .filter(|h| !visited.contains(h))
+            .collect();
+        if group.len() > 1 {
+            for h in group.iter() {
+                visited.insert(h.clone());
+            }
+            clusters.push(group);
+        } else {
+            visited.insert(hash.clone());
+        }
+    }
+    clusters
+}